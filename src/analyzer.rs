@@ -3,87 +3,338 @@
 use crate::model::*;
 use git2::{Commit, Diff, DiffOptions, Oid, Repository, Tree};
 use indicatif::ProgressBar;
-use std::collections::{HashMap, BTreeMap};
-use std::path::Path;
+use std::collections::{HashMap, HashSet, BTreeMap, BinaryHeap};
+use std::path::{Path, PathBuf};
 
-pub fn analyze(repo_path: &Path) -> Result<AnalysisResult, git2::Error> {
-    let repo = Repository::open(repo_path)?;
-    println!("Analyzing repository at: {}", repo_path.display());
+/// Parses a `YYYY-MM-DD` date into a UTC timestamp at the start of that day.
+fn parse_date(s: &str) -> Result<i64, git2::Error> {
+    use chrono::{NaiveDate, NaiveTime};
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| git2::Error::from_str(&format!("invalid date '{}': {}", s, e)))?;
+    Ok(date.and_time(NaiveTime::MIN).and_utc().timestamp())
+}
+
+/// Derives the namespace used to prefix a repository's file paths.
+fn repo_name(repo_path: &Path) -> String {
+    repo_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(String::from)
+        .unwrap_or_else(|| repo_path.display().to_string())
+}
 
-    // 1. Collect all commits and sort them chronologically
+/// Walks one repository and returns its commits (deduplicated by `Oid`, oldest first).
+fn collect_commits(repo: &Repository, branches: &[String]) -> Result<Vec<(Oid, i64)>, git2::Error> {
     let mut commits = Vec::new();
     let mut revwalk = repo.revwalk()?;
-    revwalk.push_head()?;
+    if branches.is_empty() {
+        revwalk.push_head()?;
+    } else {
+        for name in branches {
+            let commit = repo.revparse_single(name)?.peel_to_commit()?;
+            revwalk.push(commit.id())?;
+        }
+    }
     revwalk.set_sorting(git2::Sort::TIME)?;
 
+    // Deduplicate by Oid so commits shared between branches aren't double-counted
+    let mut seen: HashSet<Oid> = HashSet::new();
     for oid in revwalk {
         let oid = oid?;
+        if !seen.insert(oid) {
+            continue;
+        }
         let commit = repo.find_commit(oid)?;
         commits.push((oid, commit.time().seconds()));
     }
     commits.reverse(); // Walk from the first commit to the last
+    Ok(commits)
+}
+
+pub fn analyze(
+    repo_paths: &[PathBuf],
+    branches: &[String],
+    since: Option<&str>,
+    until: Option<&str>,
+    window_days: u64,
+) -> Result<AnalysisResult, git2::Error> {
+    // 1. Open every repository and collect its commit list up front so the analysis
+    //    window can be resolved against the combined history.
+    let mut repos = Vec::new();
+    for repo_path in repo_paths {
+        println!("Analyzing repository at: {}", repo_path.display());
+        let repo = Repository::open(repo_path)?;
+        let commits = collect_commits(&repo, branches)?;
+        repos.push((repo_name(repo_path), repo, commits));
+    }
 
-    let bar = ProgressBar::new(commits.len() as u64);
+    let history_end = repos.iter().flat_map(|(_, _, c)| c.last()).map(|&(_, ts)| ts).max().unwrap_or(0);
+
+    // Resolve the analysis window. `until` defaults to the last commit and `since`
+    // defaults to one year before `until` so a bare run animates the last year.
+    const ONE_YEAR_SECS: i64 = 365 * 24 * 60 * 60;
+    let until = match until {
+        Some(s) => parse_date(s)?,
+        None => history_end,
+    };
+    let since = match since {
+        Some(s) => parse_date(s)?,
+        None => until - ONE_YEAR_SECS,
+    };
+    let start_time = since;
+    let end_time = until;
+
+    // Resolve the describe-style caption for every analyzed commit, per repository.
+    let mut describe_labels: HashMap<Oid, String> = HashMap::new();
+    for (_, repo, commits) in &repos {
+        describe_labels.extend(describe_commits(repo, commits)?);
+    }
+
+    let total_commits: usize = repos.iter().map(|(_, _, c)| c.len()).sum();
+    let bar = ProgressBar::new(total_commits as u64);
     bar.set_message("Analyzing commits");
 
-    // --- Analysis State ---
+    // --- Shared analysis state, merged across all repositories ---
+    // File ids stay globally unique via `next_file_id`, committers are unified by
+    // author name, and file paths/keys are namespaced with the repo name.
     let mut file_map: HashMap<String, FileId> = HashMap::new();
     let mut file_infos: Vec<FileInfo> = Vec::new();
     let mut next_file_id = 0;
     let mut change_map: ChangeMap = HashMap::new();
     let mut committer_map: HashMap<String, CommitterId> = HashMap::new();
     let mut committers: Vec<String> = Vec::new();
+    let mut all_commits = Vec::new();
 
-    let start_time = commits.first().map_or(0, |&(_, ts)| ts);
-    let end_time = commits.last().map_or(0, |&(_, ts)| ts);
+    // 2. Iterate through each repository's commits and process diffs
+    for (name, repo, commits) in &repos {
+        let prefix = format!("{}/", name);
+        for (oid, _) in commits.iter() {
+            let commit = repo.find_commit(*oid)?;
+            let commit_time = commit.time().seconds();
 
-    // 2. Iterate through commits and process diffs
-    for (i, (oid, _)) in commits.iter().enumerate() {
-        let commit = repo.find_commit(*oid)?;
-        let commit_time = commit.time().seconds();
+            // Diff against the commit's actual first parent rather than the previous
+            // entry in the time-sorted list: with merged multi-branch history the
+            // chronological predecessor often lives on a different branch, which would
+            // attribute unrelated cross-branch deltas to this commit.
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let current_tree = commit.tree()?;
 
-        let parent_tree = if i > 0 {
-            let parent_commit = repo.find_commit(commits[i - 1].0)?;
-            Some(parent_commit.tree()?)
-        } else {
-            None
-        };
-        let current_tree = commit.tree()?;
+            let mut diff_opts = DiffOptions::new();
+            diff_opts.include_untracked(false);
+            diff_opts.ignore_filemode(true);
+            diff_opts.enable_fast_untracked_dirs(true);
+            diff_opts.find_renames(true);
 
-        let mut diff_opts = DiffOptions::new();
-        diff_opts.include_untracked(false);
-        diff_opts.ignore_filemode(true);
-        diff_opts.enable_fast_untracked_dirs(true);
-        diff_opts.find_renames(true);
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&current_tree), Some(&mut diff_opts))?;
 
-        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&current_tree), Some(&mut diff_opts))?;
-        
-        process_diff(&diff, &commit, &repo, &mut file_map, &mut file_infos, &mut next_file_id, &mut change_map, &mut committer_map, &mut committers)?;
+            // File births/deaths are tracked across the whole history so lifecycles stay
+            // correct, but per-line changes are only recorded inside the analysis window.
+            let in_window = commit_time >= since && commit_time <= until;
+            process_diff(&diff, &commit, repo, &prefix, &mut file_map, &mut file_infos, &mut next_file_id, &mut change_map, &mut committer_map, &mut committers, in_window)?;
 
-        bar.inc(1);
+            bar.inc(1);
+        }
+        all_commits.extend(commits.iter().copied());
     }
     bar.finish_with_message("Analysis complete");
 
+    // Precompute the sliding-window heat timeline so the renderer never rescans a
+    // line's full history per frame (see `build_heat_timeline`).
+    let window_seconds = window_days as i64 * 24 * 60 * 60;
+    let heat_timeline = build_heat_timeline(&change_map, start_time, end_time, window_seconds);
+
     Ok(AnalysisResult {
         files: file_infos,
         changes: change_map,
         committers,
         start_time,
         end_time,
-        commits,
+        commits: all_commits,
+        describe_labels,
+        heat_timeline,
     })
 }
 
+/// Turns each line's change history into a compact `(frame_index, heat)` breakpoint
+/// list via an event-driven sweep: a change at `t` adds `+1` over `[t, t + window)`,
+/// emitted as `+1`/`-1` deltas at the corresponding frame indices and then prefix-summed
+/// once. This replaces the renderer's per-frame O(history) rescan with a binary search
+/// over the line's breakpoints.
+fn build_heat_timeline(changes: &ChangeMap, start_time: i64, end_time: i64, window_seconds: i64) -> HeatTimeline {
+    let total_frames = (end_time - start_time).max(0) / 60;
+    // Maps a timestamp to its frame index, clamped into the rendered range.
+    let frame_of = |t: i64| -> i64 { ((t - start_time) / 60).clamp(0, total_frames + 1) };
+
+    let mut timeline = HeatTimeline::new();
+    for (&key, history) in changes {
+        let mut deltas: BTreeMap<i64, i32> = BTreeMap::new();
+        for change in history {
+            *deltas.entry(frame_of(change.timestamp)).or_insert(0) += 1;
+            *deltas.entry(frame_of(change.timestamp + window_seconds)).or_insert(0) -= 1;
+        }
+
+        // Prefix-sum the deltas, emitting a breakpoint only where the heat changes.
+        let mut breakpoints = Vec::new();
+        let mut heat: i32 = 0;
+        for (frame, delta) in deltas {
+            heat += delta;
+            let heat = heat.max(0) as u32;
+            if breakpoints.last().map_or(true, |&(_, h)| h != heat) {
+                breakpoints.push((frame, heat));
+            }
+        }
+        if !breakpoints.is_empty() {
+            timeline.insert(key, breakpoints);
+        }
+    }
+    timeline
+}
+
+/// An annotated tag considered as a describe candidate.
+struct TagCandidate {
+    bit: u32,
+    oid: Oid,
+    name: String,
+    time: i64,
+}
+
+/// Resolves a describe-style caption (nearest reachable tag) for every commit in
+/// `commits`, mirroring git's "describe" algorithm with a 32-bit candidate field.
+fn describe_commits(repo: &Repository, commits: &[(Oid, i64)]) -> Result<HashMap<Oid, String>, git2::Error> {
+    let analyzed: HashSet<Oid> = commits.iter().map(|&(o, _)| o).collect();
+
+    // Collect up to 32 annotated tags that point into the analyzed history, giving
+    // each a distinct bit so flags fit in a single u32.
+    let mut candidates: Vec<TagCandidate> = Vec::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        if candidates.len() >= 32 {
+            break;
+        }
+        let obj = match repo.revparse_single(&format!("refs/tags/{}", name)) {
+            Ok(obj) => obj,
+            Err(_) => continue,
+        };
+        // Only annotated tags carry a tag object; peel it to its target commit.
+        let tag = match obj.as_tag() {
+            Some(tag) => tag,
+            None => continue,
+        };
+        let target = match obj.peel_to_commit() {
+            Ok(c) => c.id(),
+            Err(_) => continue,
+        };
+        if !analyzed.contains(&target) {
+            continue;
+        }
+        let time = tag.tagger().map(|s| s.when().seconds()).unwrap_or_default();
+        candidates.push(TagCandidate { bit: 1 << candidates.len(), oid: target, name: name.to_string(), time });
+    }
+
+    let mut labels = HashMap::new();
+    if candidates.is_empty() {
+        return Ok(labels);
+    }
+    for &(oid, _) in commits {
+        if let Some(label) = describe_one(repo, oid, &candidates)? {
+            labels.insert(oid, label);
+        }
+    }
+    Ok(labels)
+}
+
+/// Runs the describe walk for a single target commit and returns its caption.
+fn describe_one(repo: &Repository, target: Oid, candidates: &[TagCandidate]) -> Result<Option<String>, git2::Error> {
+    let all_bits: u32 = candidates.iter().fold(0, |acc, c| acc | c.bit);
+    let seed: HashMap<Oid, u32> = candidates.iter().map(|c| (c.oid, c.bit)).collect();
+
+    // Commit-time-ordered (most recent first) traversal of the target's ancestry.
+    // Each commit is counted once; flags accumulate via the per-commit `flags` map
+    // before the commit is popped, since parents are always older than their children.
+    let mut flags: HashMap<Oid, u32> = HashMap::new();
+    let mut processed: HashSet<Oid> = HashSet::new();
+    let mut heap: BinaryHeap<(i64, Oid)> = BinaryHeap::new();
+    let target_time = repo.find_commit(target)?.time().seconds();
+    flags.insert(target, 0);
+    heap.push((target_time, target));
+
+    // `ahead[i]` counts commits reachable from the target that lack candidate i's bit.
+    let mut ahead = vec![0usize; candidates.len()];
+    let mut seeded_frontier: u32 = 0;
+
+    while let Some((_, oid)) = heap.pop() {
+        if !processed.insert(oid) {
+            continue;
+        }
+        let mut f = *flags.get(&oid).unwrap_or(&0);
+        if let Some(&bit) = seed.get(&oid) {
+            f |= bit;
+            flags.insert(oid, f);
+        }
+
+        for (i, c) in candidates.iter().enumerate() {
+            if f & c.bit == 0 {
+                ahead[i] += 1;
+            }
+        }
+
+        seeded_frontier |= f;
+        // Stop propagating once every candidate's bit has entered the frontier.
+        if seeded_frontier == all_bits {
+            break;
+        }
+
+        let commit = repo.find_commit(oid)?;
+        for parent in commit.parent_ids() {
+            let newly_seen = !flags.contains_key(&parent);
+            *flags.entry(parent).or_insert(0) |= f;
+            if newly_seen {
+                let ptime = repo.find_commit(parent)?.time().seconds();
+                heap.push((ptime, parent));
+            }
+        }
+    }
+
+    // A candidate is only reachable if at least one traversed commit carried its bit;
+    // an unreachable tag is counted as "ahead" for every commit we processed.
+    let processed_count = processed.len();
+
+    // Best candidate: smallest ahead distance, ties broken by the most recent tag.
+    let best = candidates
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| ahead[*i] < processed_count)
+        .min_by(|(ia, ca), (ib, cb)| {
+            ahead[*ia].cmp(&ahead[*ib]).then(cb.time.cmp(&ca.time))
+        });
+
+    Ok(best.map(|(i, c)| {
+        if ahead[i] == 0 {
+            c.name.clone()
+        } else {
+            format!("{}-{}-g{}", c.name, ahead[i], short_oid(target))
+        }
+    }))
+}
+
+fn short_oid(oid: Oid) -> String {
+    oid.to_string()[..7].to_string()
+}
+
 fn process_diff<'a>(
     diff: &'a Diff<'a>,
     commit: &Commit,
     repo: &Repository,
+    prefix: &str,
     file_map: &mut HashMap<String, FileId>,
     file_infos: &mut Vec<FileInfo>,
     next_file_id: &mut FileId,
     change_map: &mut ChangeMap,
     committer_map: &mut HashMap<String, CommitterId>,
     committers: &mut Vec<String>,
+    in_window: bool,
 ) -> Result<(), git2::Error> {
     let commit_time = commit.time().seconds();
     let author = commit.author();
@@ -97,8 +348,8 @@ fn process_diff<'a>(
 
     diff.foreach(
         &mut |delta, _| {
-            let old_path = delta.old_file().path().and_then(|p| p.to_str()).map(String::from);
-            let new_path = delta.new_file().path().and_then(|p| p.to_str()).map(String::from);
+            let old_path = delta.old_file().path().and_then(|p| p.to_str()).map(|p| format!("{}{}", prefix, p));
+            let new_path = delta.new_file().path().and_then(|p| p.to_str()).map(|p| format!("{}{}", prefix, p));
 
             match delta.status() {
                 git2::Delta::Added => {
@@ -140,7 +391,8 @@ fn process_diff<'a>(
         None,
         Some(&mut |delta, hunk| {
             if let Some(path_str) = delta.new_file().path().and_then(|p| p.to_str()) {
-                if let Some(&file_id) = file_map.get(path_str) {
+                let path_str = format!("{}{}", prefix, path_str);
+                if let Some(&file_id) = file_map.get(&path_str) {
                     let blob = repo.find_blob(delta.new_file().id()).ok();
                     let line_count = blob.map_or(0, |b| b.content().lines().count());
                     file_infos[file_id].line_counts.insert(commit_time, line_count);
@@ -158,7 +410,7 @@ fn process_diff<'a>(
                                 diff.foreach_line(
                                     &mut |_, line| {
                                         match line.origin() {
-                                            '+' | '-' => {
+                                            '+' | '-' if in_window => {
                                                 // A change occurred at this line number
                                                 let history = change_map.entry((file_id, current_line_no)).or_default();
                                                 history.push(LineChange { timestamp: commit_time, committer_id });
@@ -6,14 +6,26 @@ use std::path::PathBuf;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Path to the git repository to analyze
-    #[arg(short, long)]
-    pub repo: PathBuf,
+    /// Paths to the git repositories to analyze; multiple repos are merged into one timeline
+    #[arg(short, long = "repos", num_args = 1.., required = true)]
+    pub repos: Vec<PathBuf>,
+
+    /// Refs to traverse (branches, tags, etc.); defaults to HEAD when omitted
+    #[arg(long, num_args = 0..)]
+    pub branches: Vec<String>,
 
     /// Directory to save the output PNG frames
     #[arg(short, long)]
     pub output: PathBuf,
 
+    /// Only analyze commits on or after this date (YYYY-MM-DD); defaults to one year before `until`
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only analyze commits on or before this date (YYYY-MM-DD); defaults to the last commit
+    #[arg(long)]
+    pub until: Option<String>,
+
     /// Width of the output images in pixels
     #[arg(long, default_value_t = 1280)]
     pub width: u32,
@@ -29,6 +41,28 @@ pub struct Args {
     /// Visualization mode
     #[arg(long, value_enum, default_value_t = Mode::HotCold)]
     pub mode: Mode,
+
+    /// Color scheme for the heat gradient and committer palette
+    #[arg(long, value_enum, default_value_t = ColorScheme::BlueOrange)]
+    pub color_scheme: ColorScheme,
+
+    /// Restrict counts to a single author (by name); used by the calendar mode
+    #[arg(long)]
+    pub author: Option<String>,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug, Copy)]
+pub enum ColorScheme {
+    /// Blue (cold) to orange (hot)
+    BlueOrange,
+    /// Dark to bright green
+    Green,
+    /// Dark to bright red
+    Red,
+    /// Perceptually-uniform viridis (purple to yellow)
+    Viridis,
+    /// Colorblind-safe dark-to-light grayscale
+    Grayscale,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug, Copy)]
@@ -37,4 +71,6 @@ pub enum Mode {
     HotCold,
     /// Color lines by the last committer within the window
     Committer,
+    /// GitHub-style contribution calendar that fills in one day per frame
+    Calendar,
 }
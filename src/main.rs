@@ -2,6 +2,7 @@
 
 mod analyzer;
 mod cli;
+mod font;
 mod model;
 mod renderer;
 
@@ -14,7 +15,7 @@ fn main() {
     let args = Args::parse();
     let start_time = Instant::now();
 
-    match analyzer::analyze(&args.repo) {
+    match analyzer::analyze(&args.repos, &args.branches, args.since.as_deref(), args.until.as_deref(), args.window_days) {
         Ok(analysis_result) => {
             println!("Analysis finished in {:.2?}. Found {} files, {} committers.", start_time.elapsed(), analysis_result.files.len(), analysis_result.committers.len());
             println!("Repository history spans from {} to {}.",
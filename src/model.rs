@@ -1,6 +1,7 @@
 // src/model.rs
 
 use std::collections::{HashMap, BTreeMap};
+use git2::Oid;
 
 /// Uniquely identifies a committer
 pub type CommitterId = usize;
@@ -21,6 +22,11 @@ pub type LineHistory = Vec<LineChange>;
 /// Maps a (FileId, line_number) pair to its change history
 pub type ChangeMap = HashMap<(FileId, usize), LineHistory>;
 
+/// Precomputed sliding-window heat for a `(FileId, line_number)` pair, stored as a
+/// sorted list of `(frame_index, heat)` breakpoints. The heat at a frame is the value
+/// of the last breakpoint whose `frame_index` is `<= frame`.
+pub type HeatTimeline = HashMap<(FileId, usize), Vec<(i64, u32)>>;
+
 /// Information about a file's lifecycle and properties
 #[derive(Debug, Clone)]
 pub struct FileInfo {
@@ -41,4 +47,8 @@ pub struct AnalysisResult {
     pub start_time: i64,
     pub end_time: i64,
     pub commits: Vec<(git2::Oid, i64)>,
+    /// Describe-style caption (nearest reachable tag) resolved per commit
+    pub describe_labels: HashMap<Oid, String>,
+    /// Precomputed per-line heat over the animation's frames
+    pub heat_timeline: HeatTimeline,
 }
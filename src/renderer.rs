@@ -1,53 +1,62 @@
 // src/renderer.rs
 
-use crate::cli::{Args, Mode};
+use crate::cli::{Args, ColorScheme, Mode};
 use crate::model::*;
-use chrono::Duration;
+use chrono::{DateTime, Datelike, NaiveDate};
 use image::{Rgb, RgbImage};
 use indicatif::{ParallelProgressIterator, ProgressBar};
 use palette::{FromColor, Lch, LinSrgb, Srgb};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 
 pub fn render_frames(analysis: &AnalysisResult, args: &Args) {
     fs::create_dir_all(&args.output).expect("Failed to create output directory");
 
-    let window_seconds = Duration::days(args.window_days as i64).num_seconds();
+    if let Mode::Calendar = args.mode {
+        render_calendar(analysis, args);
+        return;
+    }
+
     let total_minutes = (analysis.end_time - analysis.start_time) / 60;
     
     let bar = ProgressBar::new(total_minutes as u64);
     bar.set_message("Rendering frames");
 
     // Pre-generate committer colors for consistency
-    let committer_colors = generate_committer_colors(analysis.committers.len());
+    let committer_colors = generate_committer_colors(analysis.committers.len(), args.color_scheme);
 
-    // Create a BTreeMap of commit time -> state for quick lookups
-    // This simplifies finding the active state for any given minute.
+    // Create a BTreeMap of commit time -> Oid for quick lookups. This simplifies
+    // finding the active commit (and its describe caption) for any given minute.
     let mut commit_times = BTreeMap::new();
-    for &(_, ts) in &analysis.commits {
-        commit_times.insert(ts, ());
+    for &(oid, ts) in &analysis.commits {
+        commit_times.insert(ts, oid);
     }
 
     (0..=total_minutes).into_par_iter().progress_with(bar).for_each(|i| {
         let current_time = analysis.start_time + i * 60;
         let frame_path = args.output.join(format!("frame_{:06}.png", i));
 
-        // Find the most recent commit time that is <= current_time
-        let active_commit_time = commit_times.range(..=current_time).next_back().map_or(analysis.start_time, |(&ts, _)| ts);
+        // Find the most recent commit that is <= current_time
+        let active = commit_times.range(..=current_time).next_back();
+        let active_commit_time = active.map_or(analysis.start_time, |(&ts, _)| ts);
+        let caption = active.and_then(|(_, oid)| analysis.describe_labels.get(oid));
 
         let mut image = RgbImage::new(args.width, args.height);
         render_frame(
             &mut image,
             current_time,
             active_commit_time,
-            window_seconds,
             analysis,
             args,
             &committer_colors,
         );
+
+        if let Some(label) = caption {
+            crate::font::draw_text(&mut image, label, 8, 8, 2, Rgb([235, 235, 235]));
+        }
         image.save(&frame_path).expect("Failed to save frame");
     });
 }
@@ -56,7 +65,6 @@ fn render_frame(
     image: &mut RgbImage,
     current_time: i64,
     active_commit_time: i64,
-    window_seconds: i64,
     analysis: &AnalysisResult,
     args: &Args,
     committer_colors: &[Rgb<u8>],
@@ -84,11 +92,11 @@ fn render_frame(
         let line_count = file_info.line_counts.range(..=active_commit_time).next_back().map_or(0, |(_, &c)| c);
         for line_num in 0..line_count {
             if let Some(history) = analysis.changes.get(&(file_info.id, line_num + 1)) {
-                 let window_start = current_time - window_seconds;
-                 
                  match args.mode {
                     Mode::HotCold => {
-                        let heat = history.iter().filter(|c| c.timestamp >= window_start && c.timestamp <= current_time).count();
+                        // Binary-search lookup into the precomputed heat timeline instead of a rescan.
+                        let frame = ((current_time - analysis.start_time) / 60).max(0);
+                        let heat = heat_at(&analysis.heat_timeline, file_info.id, line_num + 1, frame);
                         line_data_cache.insert((file_idx, line_num), (heat, 0)); // 0 for committer_id is unused
                     }
                     Mode::Committer => {
@@ -100,6 +108,8 @@ fn render_frame(
                             line_data_cache.insert((file_idx, line_num), (0, id)); // 0 for heat is unused
                         }
                     }
+                    // Calendar mode is handled by a dedicated renderer, not per-line.
+                    Mode::Calendar => {}
                  }
             }
         }
@@ -118,8 +128,9 @@ fn render_frame(
 
         if let Some(&(heat, committer_id)) = line_data_cache.get(&(file_idx, line_num)) {
             *pixel = match args.mode {
-                Mode::HotCold => heat_to_color(heat),
+                Mode::HotCold => heat_to_color(heat, args.color_scheme),
                 Mode::Committer => committer_colors.get(committer_id).unwrap_or(&bg_color).clone(),
+                Mode::Calendar => bg_color,
             };
         } else {
             *pixel = bg_color;
@@ -127,49 +138,98 @@ fn render_frame(
     }
 }
 
-// Blue-to-Orange color gradient for hotness
-fn heat_to_color(heat: usize) -> Rgb<u8> {
-    let lch_colors = vec![
-        Lch::new(20.0f32, 30.0f32, 250.0f32), // Dark Blue
-        Lch::new(40.0f32, 40.0f32, 260.0f32), // Blue
-        Lch::new(95.0f32, 35.0f32, 90.0f32),  // Light Yellow
-        Lch::new(75.0f32, 80.0f32, 50.0f32),  // Orange
-        Lch::new(65.0f32, 100.0f32, 30.0f32), // Red-Orange
-    ];
-    let gradient_stops: Vec<LinSrgb<f32>> = lch_colors.into_iter().map(LinSrgb::from_color).collect();
-
-    // Clamp heat for a reasonable visual range and scale to gradient size
-    let heat_float = (heat as f32 / 10.0f32).min(1.0f32);
-    let scaled_pos = heat_float * (gradient_stops.len() - 1) as f32;
+// Looks up the precomputed heat for a line at a given frame: the heat of the last
+// breakpoint whose frame index is <= `frame`.
+fn heat_at(timeline: &HeatTimeline, file_id: FileId, line: usize, frame: i64) -> usize {
+    match timeline.get(&(file_id, line)) {
+        Some(breakpoints) => {
+            let idx = breakpoints.partition_point(|&(f, _)| f <= frame);
+            if idx == 0 { 0 } else { breakpoints[idx - 1].1 as usize }
+        }
+        None => 0,
+    }
+}
 
+// The LCH gradient stops used for the hotness ramp, selected by color scheme.
+fn gradient_stops(scheme: ColorScheme) -> Vec<Lch<f32>> {
+    match scheme {
+        ColorScheme::BlueOrange => vec![
+            Lch::new(20.0f32, 30.0f32, 250.0f32), // Dark Blue
+            Lch::new(40.0f32, 40.0f32, 260.0f32), // Blue
+            Lch::new(95.0f32, 35.0f32, 90.0f32),  // Light Yellow
+            Lch::new(75.0f32, 80.0f32, 50.0f32),  // Orange
+            Lch::new(65.0f32, 100.0f32, 30.0f32), // Red-Orange
+        ],
+        ColorScheme::Green => vec![
+            Lch::new(20.0f32, 20.0f32, 140.0f32),
+            Lch::new(50.0f32, 55.0f32, 135.0f32),
+            Lch::new(85.0f32, 90.0f32, 130.0f32),
+        ],
+        ColorScheme::Red => vec![
+            Lch::new(20.0f32, 25.0f32, 25.0f32),
+            Lch::new(50.0f32, 70.0f32, 30.0f32),
+            Lch::new(80.0f32, 100.0f32, 35.0f32),
+        ],
+        ColorScheme::Viridis => vec![
+            Lch::new(15.0f32, 40.0f32, 300.0f32), // Dark Purple
+            Lch::new(40.0f32, 45.0f32, 270.0f32), // Blue
+            Lch::new(60.0f32, 40.0f32, 180.0f32), // Teal
+            Lch::new(90.0f32, 90.0f32, 100.0f32), // Yellow-Green
+        ],
+        ColorScheme::Grayscale => vec![
+            Lch::new(15.0f32, 0.0f32, 0.0f32),
+            Lch::new(95.0f32, 0.0f32, 0.0f32),
+        ],
+    }
+}
+
+// Samples a linear-sRGB gradient at `t` in `[0, 1]` and returns the sRGB pixel.
+fn sample_gradient(stops: &[LinSrgb<f32>], t: f32) -> Rgb<u8> {
+    let scaled_pos = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
     let idx1 = scaled_pos.floor() as usize;
-    let idx2 = (idx1 + 1).min(gradient_stops.len() - 1);
-    let t = scaled_pos.fract();
+    let idx2 = (idx1 + 1).min(stops.len() - 1);
+    let frac = scaled_pos.fract();
 
-    let c1 = gradient_stops[idx1];
-    let c2 = gradient_stops[idx2];
+    let c1 = stops[idx1];
+    let c2 = stops[idx2];
 
     // Manual linear interpolation
-    let r = c1.red + (c2.red - c1.red) * t;
-    let g = c1.green + (c2.green - c1.green) * t;
-    let b = c1.blue + (c2.blue - c1.blue) * t;
-    let final_color = LinSrgb::new(r, g, b);
+    let r = c1.red + (c2.red - c1.red) * frac;
+    let g = c1.green + (c2.green - c1.green) * frac;
+    let b = c1.blue + (c2.blue - c1.blue) * frac;
 
     // Convert from linear sRGB to standard sRGB
-    let srgb = Srgb::from_linear(final_color);
+    let srgb = Srgb::from_linear(LinSrgb::new(r, g, b));
     let (r, g, b) = srgb.into_components();
-    let r_u8 = (r * 255.0f32) as u8;
-    let g_u8 = (g * 255.0f32) as u8;
-    let b_u8 = (b * 255.0f32) as u8;
-    Rgb([r_u8, g_u8, b_u8])
+    Rgb([(r * 255.0f32) as u8, (g * 255.0f32) as u8, (b * 255.0f32) as u8])
 }
 
-fn generate_committer_colors(num_committers: usize) -> Vec<Rgb<u8>> {
+// Heat-to-color mapping along the selected color scheme's gradient.
+fn heat_to_color(heat: usize, scheme: ColorScheme) -> Rgb<u8> {
+    let stops: Vec<LinSrgb<f32>> = gradient_stops(scheme).into_iter().map(LinSrgb::from_color).collect();
+    // Clamp heat for a reasonable visual range and scale onto the gradient.
+    sample_gradient(&stops, heat as f32 / 10.0f32)
+}
+
+fn generate_committer_colors(num_committers: usize, scheme: ColorScheme) -> Vec<Rgb<u8>> {
     let mut rng = StdRng::seed_from_u64(42); // Seed for deterministic colors
+    // Each scheme fixes the palette base: a hue band for the colored schemes, or a
+    // lightness spread (with zero chroma) for grayscale.
+    let (hue_center, hue_spread): (f32, f32) = match scheme {
+        ColorScheme::BlueOrange | ColorScheme::Viridis => (180.0, 180.0), // full hue circle
+        ColorScheme::Green => (135.0, 45.0),
+        ColorScheme::Red => (30.0, 30.0),
+        ColorScheme::Grayscale => (0.0, 0.0),
+    };
     (0..num_committers)
         .map(|_| {
-            let hue = rng.gen_range(0.0f32..360.0f32);
-            let color = Lch::new(70.0f32, 80.0f32, hue); // Bright, saturated colors
+            let color = if matches!(scheme, ColorScheme::Grayscale) {
+                let lightness = rng.gen_range(35.0f32..95.0f32);
+                Lch::new(lightness, 0.0f32, 0.0f32)
+            } else {
+                let hue = hue_center + rng.gen_range(-hue_spread..hue_spread);
+                Lch::new(70.0f32, 80.0f32, hue) // Bright, saturated colors
+            };
             let srgb: Srgb<f32> = Srgb::from_color(color);
             let (r, g, b) = srgb.into_components();
             let r_u8 = (r * 255.0f32) as u8;
@@ -179,3 +239,97 @@ fn generate_committer_colors(num_committers: usize) -> Vec<Rgb<u8>> {
         })
         .collect()
 }
+
+/// Renders the `Calendar` mode: a GitHub-style 7-row (Mon–Sun) by N-week grid whose
+/// cells encode daily `LineChange` counts, emitting one growing frame per day.
+fn render_calendar(analysis: &AnalysisResult, args: &Args) {
+    // Optional author filter: the committer ids whose changes should be counted.
+    let allowed: Option<HashSet<CommitterId>> = args.author.as_ref().map(|name| {
+        analysis.committers.iter().enumerate()
+            .filter(|(_, n)| n.as_str() == name)
+            .map(|(id, _)| id)
+            .collect()
+    });
+
+    // Count LineChanges per calendar day, honoring the author filter.
+    let mut counts: HashMap<NaiveDate, usize> = HashMap::new();
+    for history in analysis.changes.values() {
+        for change in history {
+            if let Some(ids) = &allowed {
+                if !ids.contains(&change.committer_id) {
+                    continue;
+                }
+            }
+            if let Some(dt) = DateTime::from_timestamp(change.timestamp, 0) {
+                *counts.entry(dt.date_naive()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let start_date = DateTime::from_timestamp(analysis.start_time, 0).map(|d| d.date_naive());
+    let end_date = DateTime::from_timestamp(analysis.end_time, 0).map(|d| d.date_naive());
+    let (start_date, end_date) = match (start_date, end_date) {
+        (Some(s), Some(e)) if s <= e => (s, e),
+        _ => return,
+    };
+
+    // Enumerate every day in the window, one frame each.
+    let mut days = Vec::new();
+    let mut day = start_date;
+    while day <= end_date {
+        days.push(day);
+        match day.succ_opt() {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+
+    let max_count = counts.values().copied().max().unwrap_or(0);
+
+    // Grid geometry: columns are weeks measured from the Monday of the first week.
+    let base_monday = start_date - chrono::Duration::days(start_date.weekday().num_days_from_monday() as i64);
+    let total_weeks = ((end_date - base_monday).num_days() / 7 + 1).max(1) as u32;
+    let pad = 2u32;
+    let cell = ((args.width / total_weeks).min(args.height / 7)).saturating_sub(pad).max(1);
+    let bg_color = Rgb([8, 8, 12]);
+    let empty_cell = Rgb([22, 22, 30]);
+
+    let stops: Vec<LinSrgb<f32>> = gradient_stops(args.color_scheme).into_iter().map(LinSrgb::from_color).collect();
+
+    let bar = ProgressBar::new(days.len() as u64);
+    bar.set_message("Rendering calendar");
+
+    (0..days.len()).into_par_iter().progress_with(bar).for_each(|frame_idx| {
+        let mut image = RgbImage::from_pixel(args.width, args.height, bg_color);
+        for date in &days[..=frame_idx] {
+            let col = ((*date - base_monday).num_days() / 7) as u32;
+            let row = date.weekday().num_days_from_monday();
+            let x = col * (cell + pad) + pad;
+            let y = row * (cell + pad) + pad;
+
+            let count = counts.get(date).copied().unwrap_or(0);
+            let color = if count == 0 {
+                empty_cell
+            } else {
+                // Bucket counts into ~4 non-empty intensity levels along the gradient.
+                let level = ((count as f32 / max_count as f32) * 4.0).ceil().clamp(1.0, 4.0);
+                sample_gradient(&stops, level / 4.0)
+            };
+            fill_cell(&mut image, x, y, cell, color);
+        }
+        let frame_path = args.output.join(format!("frame_{:06}.png", frame_idx));
+        image.save(&frame_path).expect("Failed to save frame");
+    });
+}
+
+fn fill_cell(image: &mut RgbImage, x: u32, y: u32, size: u32, color: Rgb<u8>) {
+    for dy in 0..size {
+        for dx in 0..size {
+            let px = x + dx;
+            let py = y + dy;
+            if px < image.width() && py < image.height() {
+                image.put_pixel(px, py, color);
+            }
+        }
+    }
+}